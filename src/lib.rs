@@ -1,22 +1,129 @@
-use std::{fs, path::PathBuf};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
 
-use boa_engine::{Context, Source};
-use lazy_regex::regex;
+use boa_engine::{
+    object::FunctionObjectBuilder, Context, JsArgs, JsResult, JsValue, NativeFunction, Source,
+};
+use lazy_regex::{regex, Lazy};
 use serde::{Deserialize, Serialize};
 use swc_core::{
     ecma::ast::Program,
-    plugin::{errors::HANDLER, plugin_transform, proxies::TransformPluginProgramMetadata},
+    plugin::{
+        errors::HANDLER, metadata::TransformPluginMetadataContextKind, plugin_transform,
+        proxies::TransformPluginProgramMetadata,
+    },
 };
 
 static JS_VISITOR_IMPORT_REGEX: &lazy_regex::Lazy<lazy_regex::Regex> =
     regex!(r#"import(?:([\w*{Visitor}\n\r\t, ]+)[\s*]from)?[\s*](?:["']@swc\/core\/Visitor["'])?"#);
 const JS_VISITOR_STR: &str = include_str!("../node_modules/@swc/core/Visitor.js");
 
+enum Diagnostic {
+    Error(String),
+    Warning(String),
+}
+
+thread_local! {
+    // Messages emitted by `emitError`/`emitWarning` during a single `context.eval`
+    // call, flushed to `HANDLER` right after the visitor finishes running.
+    static DIAGNOSTICS: RefCell<Vec<Diagnostic>> = RefCell::new(Vec::new());
+}
+
+/// Caches the already-assembled (import-stripped) transform source per resolved
+/// impl path, keyed alongside the file's last-modified time so edits during
+/// development still invalidate the entry. Avoids re-reading and re-filtering
+/// the same impl file for every compiled module in a project.
+static TRANSFORM_SOURCE_CACHE: Lazy<Mutex<HashMap<PathBuf, (SystemTime, String)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Caches file contents read to satisfy a JS `require()`, keyed by resolved
+/// absolute path alongside the file's last-modified time. A module required
+/// from many files (or by many chained transforms) is only ever read from
+/// disk once; `__moduleCache` on the JS side separately avoids re-evaluating
+/// it.
+static REQUIRED_MODULE_SOURCE_CACHE: Lazy<Mutex<HashMap<PathBuf, (SystemTime, String)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Minimal CommonJS `require()` shim. Delegates path resolution and file
+/// reads to the native `__hostResolveModule` function, then evaluates the
+/// resolved source as a standard `(module, exports, require) => {...}` body
+/// and caches the result by the resolved absolute path, mirroring Node's own
+/// module cache. Only CJS semantics are supported here; `@swc/core/Visitor`
+/// remains special-cased above since it is injected without a module wrapper.
+const REQUIRE_SHIM_SOURCE: &str = r#"
+var __moduleCache = Object.create(null);
+var __currentModuleDir = "/cwd";
+function require(specifier) {
+  var resolved = __hostResolveModule(specifier, __currentModuleDir);
+  if (resolved === null) {
+    throw new Error("Cannot find module '" + specifier + "' from '" + __currentModuleDir + "'");
+  }
+  var info = JSON.parse(resolved);
+  if (__moduleCache[info.path]) {
+    return __moduleCache[info.path].exports;
+  }
+  var module = { exports: {} };
+  __moduleCache[info.path] = module;
+  var previousModuleDir = __currentModuleDir;
+  var lastSlash = Math.max(info.path.lastIndexOf("/"), info.path.lastIndexOf("\\"));
+  __currentModuleDir = info.path.substring(0, lastSlash);
+  try {
+    (function(module, exports, require) {
+      eval(info.source);
+    })(module, module.exports, require);
+  } catch (e) {
+    delete __moduleCache[info.path];
+    throw e;
+  } finally {
+    __currentModuleDir = previousModuleDir;
+  }
+  return module.exports;
+}
+"#;
+
+/// Export-stripped `@swc/core/Visitor` base class source, assembled once for
+/// the lifetime of the process rather than re-filtered on every `process` call.
+static BASE_VISITOR_SOURCE: Lazy<String> = Lazy::new(|| {
+    JS_VISITOR_STR
+        .lines()
+        .filter(|line| {
+            !line.starts_with("exports.")
+                && !line.starts_with(
+                    r#"Object.defineProperty(exports, "__esModule", { value: true });"#,
+                )
+        })
+        .collect::<Vec<&str>>()
+        .join("\n")
+});
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwcMetadataContext {
+    filename: Option<String>,
+    env: Option<String>,
+    cwd: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TransformVisitorConfig {
+    pub transform_impl_path: String,
+    pub visitor_class_name: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", default)]
 pub struct TransformPluginConfig {
     pub transform_impl_path: Option<String>,
     pub visitor_class_name: Option<String>,
+    pub transforms: Option<Vec<TransformVisitorConfig>>,
+    pub plugin_options: Option<serde_json::Value>,
 }
 
 impl Default for TransformPluginConfig {
@@ -24,15 +131,65 @@ impl Default for TransformPluginConfig {
         Self {
             transform_impl_path: None,
             visitor_class_name: None,
+            transforms: None,
+            plugin_options: None,
         }
     }
 }
 
-struct TransformContext {
+struct TransformEntry {
+    pub resolved_path: PathBuf,
+    pub mtime: SystemTime,
     pub transform_impl: String,
     pub transform_visitor_class_name: String,
 }
 
+struct TransformContext {
+    pub transforms: Vec<TransformEntry>,
+    pub plugin_options: serde_json::Value,
+}
+
+/// Caps how many distinct pipelines are kept warm per thread, so repeatedly
+/// editing a watched transform file during development (each edit changes its
+/// mtime, and so its cache key) doesn't leave every earlier compiled
+/// `Context` resident for the rest of the process.
+const MAX_CACHED_PIPELINES: usize = 8;
+
+thread_local! {
+    // One compiled Boa `Context` per distinct transform pipeline (resolved impl
+    // paths + mtimes + visitor class names), reused across every file in the
+    // compilation. Building it runs the base Visitor source and every chained
+    // transform's class body through `eval` exactly once; per-file work below
+    // only has to set the fresh AST/metadata/options globals and invoke the
+    // already-compiled `__runTransforms()`. Bounded to `MAX_CACHED_PIPELINES`
+    // entries, evicting the least-recently-used pipeline once full.
+    static PIPELINE_CONTEXT_CACHE: RefCell<HashMap<String, Context>> =
+        RefCell::new(HashMap::new());
+    static PIPELINE_CONTEXT_CACHE_ORDER: RefCell<VecDeque<String>> =
+        RefCell::new(VecDeque::new());
+}
+
+/// Marks `key` as the most-recently-used pipeline, inserting it if new and
+/// evicting the least-recently-used entry from both the order tracker and
+/// `PIPELINE_CONTEXT_CACHE` once `MAX_CACHED_PIPELINES` would be exceeded.
+fn touch_pipeline_cache_order(key: &str) {
+    PIPELINE_CONTEXT_CACHE_ORDER.with(|order| {
+        let mut order = order.borrow_mut();
+        if let Some(position) = order.iter().position(|cached_key| cached_key == key) {
+            order.remove(position);
+        }
+        order.push_back(key.to_string());
+
+        if order.len() > MAX_CACHED_PIPELINES {
+            if let Some(evicted_key) = order.pop_front() {
+                PIPELINE_CONTEXT_CACHE.with(|cache| {
+                    cache.borrow_mut().remove(&evicted_key);
+                });
+            }
+        }
+    });
+}
+
 #[plugin_transform]
 pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
     if let Some(transform_context) =
@@ -55,18 +212,19 @@ pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Pr
 
             let serde_serialized_ast = serde_serialized_ast.unwrap();
 
-            // Create the JavaScript context.
-            let mut context = Context::default();
-
-            // Set serialized ast into global object.
-            let set_ast_result =
-                context
-                    .global_object()
-                    .set("ast", serde_serialized_ast, true, &mut context);
-            if let Err(err) = set_ast_result {
+            // Expose the plugin metadata context (filename, env, cwd) so a JS
+            // TransformVisitor can branch on the file being transformed or the
+            // build mode, same as SWC's native plugin ABI provides it.
+            let swc_metadata_context = SwcMetadataContext {
+                filename: metadata.get_context(&TransformPluginMetadataContextKind::Filename),
+                env: metadata.get_context(&TransformPluginMetadataContextKind::Env),
+                cwd: metadata.get_context(&TransformPluginMetadataContextKind::Cwd),
+            };
+            let serde_serialized_metadata = serde_json::to_string(&swc_metadata_context);
+            if let Err(err) = serde_serialized_metadata {
                 handler.err(
                     format!(
-                        "Failed to set AST into JS context, cannot perform transform {:#?}",
+                        "Failed to serialize swc metadata into JSON, cannot perform transform {:#?}",
                         err
                     )
                     .as_str(),
@@ -75,43 +233,17 @@ pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Pr
                 return program;
             }
 
-            // Run the actual transform.
-
-            // Build base visitor class sources.
-            // Manually removing exports from the cjs module as default context does not understand it.
-            let visitor_str = JS_VISITOR_STR.lines().filter(|line| {
-                !line.starts_with("exports.")
-                    && !line.starts_with(
-                        r#"Object.defineProperty(exports, "__esModule", { value: true });"#,
-                    )
-            });
-
-            // Build custom transform visitor inherits above visitor class, actual transformer
-            // Manually removes import to the named visitor class as we inject class automatically & we don't need to
-            // resolve to the external module.
-            let transform_impl_content = transform_context
-                .transform_impl
-                .lines()
-                .filter(|line| !JS_VISITOR_IMPORT_REGEX.is_match(line));
-
-            let mut transform_codes = visitor_str
-                .chain(transform_impl_content)
-                .collect::<Vec<&str>>();
-
-            // Finally, append the actual code to perform transform.
-            let code = format!(
-                "JSON.stringify((new {}()).visitProgram(JSON.parse(ast)))",
-                transform_context.transform_visitor_class_name
-            );
-            transform_codes.push(code.as_str());
-
-            let transform_code = transform_codes.join("\n");
+            let serde_serialized_metadata = serde_serialized_metadata.unwrap();
 
-            let transform_result = context.eval(Source::from_bytes(transform_code.as_str()));
-            if let Err(err) = transform_result {
+            // Forward the user-supplied plugin options to every chained visitor's
+            // constructor, the same way the config tuple's second element reaches
+            // a native SWC plugin.
+            let serde_serialized_plugin_options =
+                serde_json::to_string(&transform_context.plugin_options);
+            if let Err(err) = serde_serialized_plugin_options {
                 handler.err(
                     format!(
-                        "Failed to run transform, cannot perform transform {:#?}",
+                        "Failed to serialize plugin options into JSON, cannot perform transform {:#?}",
                         err
                     )
                     .as_str(),
@@ -120,11 +252,72 @@ pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Pr
                 return program;
             }
 
-            let transform_result = transform_result
-                .unwrap()
-                .as_string()
-                .unwrap()
-                .to_std_string_escaped();
+            let serde_serialized_plugin_options = serde_serialized_plugin_options.unwrap();
+
+            DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().clear());
+
+            let pipeline_key = pipeline_cache_key(&transform_context.transforms);
+
+            touch_pipeline_cache_order(&pipeline_key);
+
+            let transform_result = PIPELINE_CONTEXT_CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                if !cache.contains_key(&pipeline_key) {
+                    let pipeline_context = build_pipeline_context(&transform_context.transforms)?;
+                    cache.insert(pipeline_key.clone(), pipeline_context);
+                }
+
+                let context = cache.get_mut(&pipeline_key).unwrap();
+
+                // Only the per-file JSON globals change here; the visitor
+                // pipeline itself was already compiled when this cache entry
+                // was built (or reused from an earlier file).
+                context
+                    .global_object()
+                    .set("astJson", serde_serialized_ast, true, &mut *context)
+                    .map_err(|err| format!("Failed to set AST into JS context {:#?}", err))?;
+                context
+                    .global_object()
+                    .set("swcMetadataJson", serde_serialized_metadata, true, &mut *context)
+                    .map_err(|err| {
+                        format!("Failed to set swc metadata into JS context {:#?}", err)
+                    })?;
+                context
+                    .global_object()
+                    .set(
+                        "pluginOptionsJson",
+                        serde_serialized_plugin_options,
+                        true,
+                        &mut *context,
+                    )
+                    .map_err(|err| {
+                        format!("Failed to set plugin options into JS context {:#?}", err)
+                    })?;
+
+                context
+                    .eval(Source::from_bytes("__runTransforms()"))
+                    .map_err(|err| format!("Failed to run transform {:#?}", err))
+                    .map(|value| value.as_string().unwrap().to_std_string_escaped())
+            });
+
+            // Flush whatever diagnostics the visitor emitted, win or lose, before
+            // acting on the eval result itself.
+            DIAGNOSTICS.with(|diagnostics| {
+                for diagnostic in diagnostics.borrow_mut().drain(..) {
+                    match diagnostic {
+                        Diagnostic::Error(message) => handler.err(message.as_str()),
+                        Diagnostic::Warning(message) => handler.warn(message.as_str()),
+                    }
+                }
+            });
+
+            let transform_result = match transform_result {
+                Ok(result) => result,
+                Err(err) => {
+                    handler.err(format!("{}, cannot perform transform", err).as_str());
+                    return program;
+                }
+            };
 
             let transformed_program = serde_json::from_str::<Program>(transform_result.as_str());
 
@@ -148,6 +341,129 @@ pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Pr
     }
 }
 
+/// Identifies a compiled pipeline by every input that affects the JS it
+/// compiles to: each entry's resolved path and mtime (so edited impl files
+/// invalidate the cache) plus its visitor class name.
+fn pipeline_cache_key(transforms: &[TransformEntry]) -> String {
+    transforms
+        .iter()
+        .map(|entry| {
+            let mtime_nanos = entry
+                .mtime
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_nanos())
+                .unwrap_or(0);
+            format!(
+                "{}@{}::{}",
+                entry.resolved_path.display(),
+                mtime_nanos,
+                entry.transform_visitor_class_name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Builds a fresh Boa `Context` with the base Visitor class, every chained
+/// transform's class body, and a `__runTransforms()` entry point already
+/// compiled in. Only called again for a given `pipeline_cache_key` once it's
+/// been evicted from `PIPELINE_CONTEXT_CACHE`; otherwise every later file
+/// reuses the returned context instead of re-parsing this source.
+fn build_pipeline_context(transforms: &[TransformEntry]) -> Result<Context, String> {
+    let mut context = Context::default();
+
+    let require_fn = FunctionObjectBuilder::new(
+        context.realm().clone(),
+        NativeFunction::from_fn_ptr(host_resolve_module),
+    )
+    .name("__hostResolveModule")
+    .length(2)
+    .build();
+    context
+        .global_object()
+        .set("__hostResolveModule", require_fn, true, &mut context)
+        .map_err(|err| format!("Failed to set module resolver into JS context {:#?}", err))?;
+
+    for (name, native_fn) in [
+        ("emitError", NativeFunction::from_fn_ptr(emit_error)),
+        ("emitWarning", NativeFunction::from_fn_ptr(emit_warning)),
+    ] {
+        let diagnostic_fn = FunctionObjectBuilder::new(context.realm().clone(), native_fn)
+            .name(name)
+            .length(1)
+            .build();
+        context
+            .global_object()
+            .set(name, diagnostic_fn, true, &mut context)
+            .map_err(|err| format!("Failed to set {} into JS context {:#?}", name, err))?;
+    }
+
+    // Base visitor class source is process-wide constant; assembled once and
+    // shared by every compiled pipeline instead of re-filtering it per file.
+    let mut setup_codes: Vec<String> = vec![
+        BASE_VISITOR_SOURCE.clone(),
+        REQUIRE_SHIM_SOURCE.to_string(),
+        "var swcMetadata;".to_string(),
+    ];
+
+    for (index, entry) in transforms.iter().enumerate() {
+        // A top-level `require('./helper')` inside a transform impl should
+        // resolve relative to that impl's own directory, not the project
+        // root, so each entry swaps `__currentModuleDir` to its own resolved
+        // directory for the duration of its run (nested `require()` calls
+        // still track further from there via the shim's own save/restore).
+        let entry_dir = entry
+            .resolved_path
+            .parent()
+            .unwrap_or_else(|| Path::new("/cwd"));
+        let entry_dir_js = serde_json::to_string(&entry_dir.to_string_lossy())
+            .map_err(|err| format!("Failed to encode transform impl directory {:#?}", err))?;
+
+        // The class declaration (and any top-level `require()`/setup it
+        // does) only needs to run once per pipeline, so it's hoisted into an
+        // IIFE assigned to `__ctor_N` here at build time; `__entry_N` below
+        // just instantiates the already-built class and is the only part
+        // that reruns per file. `entry.transform_impl` is already
+        // import-stripped by `cached_transform_impl`.
+        setup_codes.push(format!(
+            "var __ctor_{index} = (function() {{\n  var __previousModuleDir = __currentModuleDir;\n  __currentModuleDir = {entry_dir_js};\n  try {{\n{transform_impl}\n    return {class_name};\n  }} finally {{\n    __currentModuleDir = __previousModuleDir;\n  }}\n}})();",
+            index = index,
+            entry_dir_js = entry_dir_js,
+            transform_impl = entry.transform_impl,
+            class_name = entry.transform_visitor_class_name
+        ));
+
+        // Each chained transform still gets its own named function so
+        // visitor classes with the same name across different impl files
+        // don't collide, while still sharing the outer `Visitor` base class.
+        setup_codes.push(format!(
+            "function __entry_{index}(__ast, __pluginOptions) {{\n  var __previousModuleDir = __currentModuleDir;\n  __currentModuleDir = {entry_dir_js};\n  try {{\n    return (new __ctor_{index}(__pluginOptions)).visitProgram(__ast);\n  }} finally {{\n    __currentModuleDir = __previousModuleDir;\n  }}\n}}",
+            index = index,
+            entry_dir_js = entry_dir_js
+        ));
+    }
+
+    let pipeline_calls = (0..transforms.len())
+        .map(|index| format!("__ast = __entry_{}(__ast, __pluginOptions);", index))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Only this function's `__ast`/`__pluginOptions` locals and the `swcMetadata`
+    // global are refreshed per file; the chained entry functions above are
+    // compiled exactly once per pipeline.
+    setup_codes.push(format!(
+        "function __runTransforms() {{\n  var __ast = JSON.parse(astJson);\n  swcMetadata = JSON.parse(swcMetadataJson);\n  var __pluginOptions = JSON.parse(pluginOptionsJson);\n{}\n  return JSON.stringify(__ast);\n}}",
+        pipeline_calls
+    ));
+
+    let setup_code = setup_codes.join("\n");
+    context
+        .eval(Source::from_bytes(setup_code.as_str()))
+        .map_err(|err| format!("Failed to initialize cached transform pipeline {:#?}", err))?;
+
+    Ok(context)
+}
+
 fn build_transform_context(config_str: &Option<String>) -> Option<TransformContext> {
     HANDLER.with(|handler| {
         if config_str.is_none() {
@@ -170,36 +486,206 @@ fn build_transform_context(config_str: &Option<String>) -> Option<TransformConte
         }
 
         let deserialized_config = deserialized_config.unwrap();
-        let transform_impl_content =
-            if let Some(transform_impl_path) = &deserialized_config.transform_impl_path {
-                let mut p = PathBuf::from("/cwd");
-                p.push(transform_impl_path);
-                let content_result = fs::read_to_string(p);
-                match content_result {
-                    Ok(content) => content,
-                    Err(err) => {
-                        handler.err(
-                            format!(
-                                "Failed to read transform impl from path, skipping transform {:#?}",
-                                err
-                            )
-                            .as_str(),
-                        );
-                        return None;
-                    }
+
+        // Prefer the ordered `transforms` array; fall back to the single
+        // `transformImplPath` / `visitorClassName` pair as a shorthand for it.
+        let entries = if let Some(transforms) = &deserialized_config.transforms {
+            transforms.clone()
+        } else if let Some(transform_impl_path) = &deserialized_config.transform_impl_path {
+            vec![TransformVisitorConfig {
+                transform_impl_path: transform_impl_path.clone(),
+                visitor_class_name: deserialized_config.visitor_class_name.clone(),
+            }]
+        } else {
+            handler.err("Transform impl path is not supplied, skipping transform");
+            return None;
+        };
+
+        if entries.is_empty() {
+            handler.err("Transform impl path is not supplied, skipping transform");
+            return None;
+        }
+
+        let mut transforms = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let mut p = PathBuf::from("/cwd");
+            p.push(&entry.transform_impl_path);
+            let (mtime, transform_impl) = match cached_transform_impl(&p) {
+                Ok(result) => result,
+                Err(err) => {
+                    handler.err(
+                        format!(
+                            "Failed to read transform impl from path, skipping transform {:#?}",
+                            err
+                        )
+                        .as_str(),
+                    );
+                    return None;
                 }
-            } else {
-                handler.err("Transform impl path is not supplied, skipping transform");
-                return None;
             };
 
-        let transform_visitor_class_name = deserialized_config
-            .visitor_class_name
-            .unwrap_or("TransformVisitor".to_string());
+            let transform_visitor_class_name = entry
+                .visitor_class_name
+                .unwrap_or("TransformVisitor".to_string());
+
+            transforms.push(TransformEntry {
+                resolved_path: p,
+                mtime,
+                transform_impl,
+                transform_visitor_class_name,
+            });
+        }
+
+        let plugin_options = deserialized_config
+            .plugin_options
+            .unwrap_or(serde_json::Value::Null);
 
         return Some(TransformContext {
-            transform_impl: transform_impl_content,
-            transform_visitor_class_name,
+            transforms,
+            plugin_options,
         });
     })
 }
+
+/// Reads and import-strips the transform impl file at `path`, reusing the
+/// previous result from `TRANSFORM_SOURCE_CACHE` when the file's mtime hasn't
+/// changed since it was last cached. Returns the mtime alongside the source
+/// so callers can use it as part of a compiled-pipeline cache key.
+fn cached_transform_impl(path: &PathBuf) -> std::io::Result<(SystemTime, String)> {
+    let mtime = fs::metadata(path)?.modified()?;
+
+    let mut cache = TRANSFORM_SOURCE_CACHE.lock().unwrap();
+    if let Some((cached_mtime, cached_source)) = cache.get(path) {
+        if *cached_mtime == mtime {
+            return Ok((mtime, cached_source.clone()));
+        }
+    }
+
+    // Manually removes import to the named visitor class as we inject class automatically & we don't need to
+    // resolve to the external module.
+    let raw = fs::read_to_string(path)?;
+    let filtered = raw
+        .lines()
+        .filter(|line| !JS_VISITOR_IMPORT_REGEX.is_match(line))
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    cache.insert(path.clone(), (mtime, filtered.clone()));
+    Ok((mtime, filtered))
+}
+
+/// Native backing for the `require()` shim: resolves `specifier` relative to
+/// `from_dir` the way SWC's `NodeModulesResolver` would (relative paths
+/// as-is, bare specifiers by walking up through `node_modules`), reads the
+/// resolved file (from `REQUIRED_MODULE_SOURCE_CACHE` on a cache hit, so a
+/// module required repeatedly is only ever read from disk once), and returns
+/// `{ "path": <absolute path>, "source": <content> }` as JSON, or `null` when
+/// nothing resolves.
+fn host_resolve_module(
+    _this: &JsValue,
+    args: &[JsValue],
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    let specifier = args
+        .get_or_undefined(0)
+        .to_string(context)?
+        .to_std_string_escaped();
+    let from_dir = args
+        .get_or_undefined(1)
+        .to_string(context)?
+        .to_std_string_escaped();
+
+    match resolve_module_file(Path::new(&from_dir), &specifier) {
+        Some((path, source)) => {
+            let payload = serde_json::json!({
+                "path": path.to_string_lossy(),
+                "source": source,
+            });
+            Ok(JsValue::from(payload.to_string()))
+        }
+        None => Ok(JsValue::null()),
+    }
+}
+
+/// Native backing for the JS-global `emitError`, so a visitor can surface an
+/// actionable, file-attributed diagnostic instead of throwing an opaque JS
+/// exception. Buffered in `DIAGNOSTICS` and flushed to `HANDLER` once the
+/// visitor finishes running.
+fn emit_error(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let message = args
+        .get_or_undefined(0)
+        .to_string(context)?
+        .to_std_string_escaped();
+    DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(Diagnostic::Error(message)));
+    Ok(JsValue::undefined())
+}
+
+/// Native backing for the JS-global `emitWarning`; see [`emit_error`].
+fn emit_warning(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let message = args
+        .get_or_undefined(0)
+        .to_string(context)?
+        .to_std_string_escaped();
+    DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(Diagnostic::Warning(message)));
+    Ok(JsValue::undefined())
+}
+
+fn resolve_module_file(from_dir: &Path, specifier: &str) -> Option<(PathBuf, String)> {
+    let candidate_base = if specifier.starts_with('.') || specifier.starts_with('/') {
+        from_dir.join(specifier)
+    } else {
+        resolve_node_modules_specifier(from_dir, specifier)?
+    };
+
+    for candidate in module_candidates(&candidate_base) {
+        if let Ok(content) = cached_required_module_source(&candidate) {
+            return Some((candidate, content));
+        }
+    }
+    None
+}
+
+/// Reads the file backing a resolved `require()` specifier, reusing the
+/// previous result from `REQUIRED_MODULE_SOURCE_CACHE` when the file's mtime
+/// hasn't changed since it was last cached, so a hit skips the disk read
+/// entirely instead of just skipping re-evaluation on the JS side.
+fn cached_required_module_source(path: &Path) -> std::io::Result<String> {
+    let mtime = fs::metadata(path)?.modified()?;
+
+    let mut cache = REQUIRED_MODULE_SOURCE_CACHE.lock().unwrap();
+    if let Some((cached_mtime, cached_source)) = cache.get(path) {
+        if *cached_mtime == mtime {
+            return Ok(cached_source.clone());
+        }
+    }
+
+    let content = fs::read_to_string(path)?;
+    cache.insert(path.to_path_buf(), (mtime, content.clone()));
+    Ok(content)
+}
+
+/// `require("foo/bar")` tries `foo/bar`, `foo/bar.js` and `foo/bar/index.js`,
+/// same as Node resolves an extension-less specifier.
+fn module_candidates(base: &Path) -> Vec<PathBuf> {
+    vec![
+        base.to_path_buf(),
+        base.with_extension("js"),
+        base.join("index.js"),
+    ]
+}
+
+/// Walks `from_dir` and its ancestors looking for `node_modules/<specifier>`,
+/// the same directory-walking behavior SWC's own `NodeModulesResolver` uses.
+fn resolve_node_modules_specifier(from_dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let mut dir = from_dir.to_path_buf();
+    loop {
+        let candidate = dir.join("node_modules").join(specifier);
+        if module_candidates(&candidate).iter().any(|c| c.is_file()) {
+            return Some(candidate);
+        }
+
+        if !dir.pop() {
+            return None;
+        }
+    }
+}